@@ -1,72 +1,113 @@
 use std::{
-    ops::{Deref, DerefMut},
+    collections::{HashSet, VecDeque},
+    ops::{Deref, DerefMut, Index, IndexMut},
     time::{Duration, Instant},
 };
 
-use rand::Rng;
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
+#[derive(Clone)]
 pub enum TileMistake {
     TrippedMine,
     FlaggedField(u8),
 }
 
+#[derive(Clone)]
 pub enum TileContent {
     Mine,
     Field(u8),
     Mistake(TileMistake),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum TileMode {
     Hidden,
     Flagged,
     Revealed,
 }
 
+#[derive(Clone)]
 pub struct Tile {
     pub mode: TileMode,
     pub content: TileContent,
 }
 
-pub struct Tiles(Vec<Vec<Tile>>);
+#[derive(Clone)]
+pub struct Tiles {
+    dims: Vec<usize>,
+    cells: Vec<Tile>,
+    offsets: Vec<Vec<isize>>,
+}
 
 impl Deref for Tiles {
-    type Target = Vec<Vec<Tile>>;
+    type Target = [Tile];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.cells
     }
 }
 
 impl DerefMut for Tiles {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.cells
+    }
+}
+
+impl Index<&[usize]> for Tiles {
+    type Output = Tile;
+
+    fn index(&self, coords: &[usize]) -> &Tile {
+        &self.cells[self.flat_index(coords)]
+    }
+}
+
+impl IndexMut<&[usize]> for Tiles {
+    fn index_mut(&mut self, coords: &[usize]) -> &mut Tile {
+        let idx = self.flat_index(coords);
+        &mut self.cells[idx]
     }
 }
 
 pub struct TilesOptions {
-    pub size: (usize, usize),
-    pub starting_position: (usize, usize),
+    pub size: Vec<usize>,
+    pub starting_position: Vec<usize>,
     pub mine_count: usize,
+    pub no_guess: bool,
+    pub seed: Option<u64>,
 }
 
 enum GameState {
     Blank,
     Ongoing { started: Instant, tiles: Tiles },
-    Finished { took: Duration, tiles: Tiles },
+    Finished { took: Duration, tiles: Tiles, score: GameScore },
 }
 
 pub struct Game {
-    pub cursor: (usize, usize),
-    pub size: (usize, usize),
+    pub cursor: Vec<usize>,
+    pub size: Vec<usize>,
     state: GameState,
     mine_count: usize,
+    no_guess: bool,
+    requested_seed: Option<u64>,
+    seed: Option<u64>,
+    created_at: Instant,
+    history: Vec<(Duration, Action)>,
+    useful_clicks: usize,
+    elapsed_override: Option<Duration>,
 }
 
+#[derive(Clone, Copy)]
 pub enum CursorDirection {
-    Up,
-    Left,
-    Right,
-    Down,
+    Increase(usize),
+    Decrease(usize),
+}
+
+#[derive(Clone, Copy)]
+pub enum Action {
+    Reveal,
+    Chord,
+    Flag,
+    MoveCursor(CursorDirection),
 }
 
 pub enum GameStatus {
@@ -76,32 +117,111 @@ pub enum GameStatus {
     Ongoing,
 }
 
+#[derive(Clone, Copy)]
+pub struct GameScore {
+    pub bv3: usize,
+    pub clicks: usize,
+    pub bv3_per_second: f64,
+}
+
+pub struct Deduction {
+    pub safe: Vec<Vec<usize>>,
+    pub mines: Vec<Vec<usize>>,
+    pub guess_required: bool,
+}
+
 impl Game {
-    pub fn new(size: (usize, usize), mine_count: usize) -> Self {
+    pub fn new(size: Vec<usize>, mine_count: usize) -> Self {
+        Self::with_options(size, mine_count, false, None)
+    }
+
+    pub fn with_options(
+        size: Vec<usize>,
+        mine_count: usize,
+        no_guess: bool,
+        seed: Option<u64>,
+    ) -> Self {
         Self {
-            cursor: (0, 0),
+            cursor: vec![0; size.len()],
             size,
             state: GameState::Blank,
             mine_count,
+            no_guess,
+            requested_seed: seed,
+            seed: None,
+            created_at: Instant::now(),
+            history: Vec::new(),
+            useful_clicks: 0,
+            elapsed_override: None,
+        }
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn mine_count(&self) -> usize {
+        self.mine_count
+    }
+
+    pub fn no_guess(&self) -> bool {
+        self.no_guess
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::with_options(
+            self.size.clone(),
+            self.mine_count,
+            self.no_guess,
+            self.requested_seed,
+        );
+    }
+
+    pub fn history(&self) -> &[(Duration, Action)] {
+        &self.history
+    }
+
+    fn log(&mut self, action: Action) {
+        let at = self.created_at.elapsed();
+        self.history.push((at, action));
+    }
+
+    pub fn replay(
+        size: Vec<usize>,
+        mine_count: usize,
+        no_guess: bool,
+        seed: u64,
+        actions: Vec<(Duration, Action)>,
+    ) -> Replay {
+        Replay {
+            game: Self::with_options(size, mine_count, no_guess, Some(seed)),
+            actions,
+            next: 0,
         }
     }
 
-    pub fn status(&self) -> (Duration, GameStatus) {
+    fn snapshot(&self) -> Tiles {
         match &self.state {
-            GameState::Blank => (Duration::from_secs(0), GameStatus::Initial),
-            GameState::Ongoing { started, .. } => {
-                (Instant::now().duration_since(*started), GameStatus::Ongoing)
-            }
-            GameState::Finished { took, tiles } => {
+            GameState::Blank => Tiles::new_blank(self.size.clone()),
+            GameState::Ongoing { tiles, .. } | GameState::Finished { tiles, .. } => tiles.clone(),
+        }
+    }
+
+    pub fn status(&self) -> (Duration, GameStatus, Option<GameScore>) {
+        match &self.state {
+            GameState::Blank => (Duration::from_secs(0), GameStatus::Initial, None),
+            GameState::Ongoing { started, .. } => (
+                self.elapsed_override
+                    .unwrap_or_else(|| Instant::now().duration_since(*started)),
+                GameStatus::Ongoing,
+                None,
+            ),
+            GameState::Finished { took, tiles, score } => {
                 let lost = tiles
                     .iter()
-                    .flatten()
                     .any(|tile| matches!(tile.content, TileContent::Mistake(_)));
-                if lost {
-                    (*took, GameStatus::Lost)
-                } else {
-                    (*took, GameStatus::Won)
-                }
+                let status = if lost { GameStatus::Lost } else { GameStatus::Won };
+                (*took, status, Some(*score))
             }
         }
     }
@@ -112,12 +232,11 @@ impl Game {
         };
         let flags = tiles
             .iter()
-            .flatten()
             .filter(|tile| matches!(tile.mode, TileMode::Flagged))
             .count() as i32;
         return self.mine_count as i32 - flags;
     }
-    pub fn tile_at(&self, x: usize, y: usize) -> &Tile {
+    pub fn tile_at(&self, coords: &[usize]) -> &Tile {
         let (GameState::Ongoing { tiles, .. } | GameState::Finished { tiles, .. }) = &self.state
         else {
             return &Tile {
@@ -125,14 +244,49 @@ impl Game {
                 content: TileContent::Field(0),
             };
         };
-        &tiles[x][y]
+        &tiles[coords]
+    }
+    pub fn neighbours(&self, coords: &[usize]) -> Vec<Vec<usize>> {
+        let (GameState::Ongoing { tiles, .. } | GameState::Finished { tiles, .. }) = &self.state
+        else {
+            return Vec::new();
+        };
+        tiles.neighbours(coords)
+    }
+    pub fn deduce(&self) -> Deduction {
+        let GameState::Ongoing { tiles, .. } = &self.state else {
+            return Deduction {
+                safe: Vec::new(),
+                mines: Vec::new(),
+                guess_required: true,
+            };
+        };
+        tiles.deduce()
+    }
+    pub fn solve_step(&mut self) -> bool {
+        let GameState::Ongoing { tiles, .. } = &mut self.state else {
+            return false;
+        };
+        let deduction = tiles.deduce();
+        if deduction.safe.is_empty() && deduction.mines.is_empty() {
+            return false;
+        }
+        for coords in &deduction.mines {
+            tiles[coords.as_slice()].mode = TileMode::Flagged;
+        }
+        for coords in &deduction.safe {
+            tiles.reveal(coords);
+        }
+        self.maybe_finish();
+        true
     }
     fn finish_game(&mut self) {
+        let elapsed_override = self.elapsed_override;
         let GameState::Ongoing { tiles, started } = &mut self.state else {
             unreachable!();
         };
-        let mut tiles = std::mem::replace(tiles, Tiles::new_blank((0, 0)));
-        for tile in tiles.iter_mut().flatten() {
+        let mut tiles = std::mem::replace(tiles, Tiles::new_blank(Vec::new()));
+        for tile in tiles.iter_mut() {
             match (&tile.mode, &tile.content) {
                 (TileMode::Flagged, TileContent::Field(c)) => {
                     tile.content = TileContent::Mistake(TileMistake::FlaggedField(*c));
@@ -147,19 +301,30 @@ impl Game {
                 (_, TileContent::Mistake(_)) => unreachable!(),
             }
         }
-        let took = Instant::now().duration_since(*started);
-        self.state = GameState::Finished { took, tiles };
+        let took = elapsed_override.unwrap_or_else(|| Instant::now().duration_since(*started));
+        let bv3 = tiles.three_bv();
+        let seconds = took.as_secs_f64();
+        let score = GameScore {
+            bv3,
+            clicks: self.useful_clicks,
+            bv3_per_second: if seconds > 0.0 { bv3 as f64 / seconds } else { 0.0 },
+        };
+        self.state = GameState::Finished { took, tiles, score };
     }
     fn move_on(&mut self) {
         match self.state {
             GameState::Blank => {
+                let (tiles, effective_seed) = Tiles::new(&TilesOptions {
+                    size: self.size.clone(),
+                    starting_position: self.cursor.clone(),
+                    mine_count: self.mine_count,
+                    no_guess: self.no_guess,
+                    seed: self.requested_seed,
+                });
+                self.seed = Some(effective_seed);
                 self.state = GameState::Ongoing {
                     started: Instant::now(),
-                    tiles: Tiles::new(&TilesOptions {
-                        size: self.size,
-                        starting_position: self.cursor,
-                        mine_count: self.mine_count,
-                    }),
+                    tiles,
                 };
             }
             GameState::Finished { .. } => self.state = GameState::Blank,
@@ -170,11 +335,11 @@ impl Game {
         let GameState::Ongoing { tiles, .. } = &self.state else {
             unreachable!();
         };
-        let has_won = tiles.iter().flatten().all(|tile| {
+        let has_won = tiles.iter().all(|tile| {
             !matches!(tile.content, TileContent::Field(_))
                 || matches!(tile.mode, TileMode::Revealed)
         });
-        let has_lost = tiles.iter().flatten().any(|tile| {
+        let has_lost = tiles.iter().any(|tile| {
             matches!(
                 (&tile.mode, &tile.content),
                 (TileMode::Revealed, TileContent::Mine)
@@ -186,12 +351,13 @@ impl Game {
     }
 
     pub fn flag(&mut self) {
+        self.log(Action::Flag);
         let GameState::Ongoing { tiles, .. } = &mut self.state else {
             self.move_on();
             return;
         };
 
-        let tile = &mut tiles[self.cursor.0][self.cursor.1];
+        let tile = &mut tiles[self.cursor.as_slice()];
         tile.mode = match tile.mode {
             TileMode::Hidden => TileMode::Flagged,
             TileMode::Flagged => TileMode::Hidden,
@@ -199,126 +365,536 @@ impl Game {
         };
     }
     pub fn reveal(&mut self) {
+        self.log(Action::Reveal);
         let GameState::Ongoing { tiles, .. } = &mut self.state else {
             self.move_on();
             return;
         };
-        tiles.reveal(self.cursor.0, self.cursor.1);
+        if Self::reveal_at(tiles, &self.cursor) {
+            self.useful_clicks += 1;
+        }
         self.maybe_finish();
     }
 
+    pub fn chord(&mut self) {
+        self.log(Action::Chord);
+        let GameState::Ongoing { tiles, .. } = &mut self.state else {
+            return;
+        };
+        if !matches!(tiles[self.cursor.as_slice()].mode, TileMode::Revealed) {
+            return;
+        }
+        if Self::reveal_at(tiles, &self.cursor) {
+            self.useful_clicks += 1;
+        }
+        self.maybe_finish();
+    }
+
+    fn reveal_at(tiles: &mut Tiles, coords: &[usize]) -> bool {
+        tiles.reveal(coords) > 0
+    }
+
     pub fn move_cursor(&mut self, direction: &CursorDirection) {
-        match direction {
-            CursorDirection::Up => self.cursor.1 = self.cursor.1.saturating_sub(1),
-            CursorDirection::Down => self.cursor.1 = self.cursor.1.saturating_add(1),
-            CursorDirection::Left => self.cursor.0 = self.cursor.0.saturating_sub(1),
-            CursorDirection::Right => self.cursor.0 = self.cursor.0.saturating_add(1),
+        self.log(Action::MoveCursor(*direction));
+        let (axis, delta) = match *direction {
+            CursorDirection::Increase(axis) => (axis, 1isize),
+            CursorDirection::Decrease(axis) => (axis, -1isize),
+        };
+        let moved = self.cursor[axis] as isize + delta;
+        self.cursor[axis] = moved.clamp(0, self.size[axis] as isize - 1) as usize;
+    }
+}
+
+pub struct ReplayFrame {
+    pub before: Tiles,
+    pub after: Tiles,
+}
+
+pub struct Replay {
+    game: Game,
+    actions: Vec<(Duration, Action)>,
+    next: usize,
+}
+
+impl Replay {
+    pub fn step(&mut self) -> Option<ReplayFrame> {
+        let (at, action) = *self.actions.get(self.next)?;
+        let before = self.game.snapshot();
+        // stand in for the real clock so a fast replay doesn't make a finish
+        // look instantaneous, then fix up the stamps it left behind below
+        self.game.elapsed_override = Some(at);
+        match action {
+            Action::Reveal => self.game.reveal(),
+            Action::Chord => self.game.chord(),
+            Action::Flag => self.game.flag(),
+            Action::MoveCursor(direction) => self.game.move_cursor(&direction),
+        }
+        self.game.elapsed_override = None;
+        if let Some(last) = self.game.history.last_mut() {
+            last.0 = at;
         }
-        let size = self.size;
-        self.cursor.0 = self.cursor.0.clamp(0, size.0 - 1);
-        self.cursor.1 = self.cursor.1.clamp(0, size.1 - 1);
+        if let GameState::Ongoing { started, .. } = &mut self.game.state {
+            *started = Instant::now().checked_sub(at).unwrap_or_else(Instant::now);
+        }
+        let after = self.game.snapshot();
+        self.next += 1;
+        Some(ReplayFrame { before, after })
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn into_game(self) -> Game {
+        self.game
     }
 }
 
+const MAX_DIMS: usize = 8;
+
 impl Tiles {
-    fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
-        (-1..=1)
-            .flat_map(|x| (-1..=1).map(move |y| (x, y)))
-            .filter(|&(x_offset, y_offset)| {
-                let invalid = (x_offset == 0 && y_offset == 0)
-                    || (x_offset < 0 && x == 0)
-                    || (y_offset < 0 && y == 0)
-                    || (x_offset > 0 && x == self.len() - 1)
-                    || (y_offset > 0 && y == self[x].len() - 1);
-                !invalid
+    fn flat_index(&self, coords: &[usize]) -> usize {
+        let mut index = 0;
+        let mut stride = 1;
+        for axis in (0..self.dims.len()).rev() {
+            index += coords[axis] * stride;
+            stride *= self.dims[axis];
+        }
+        index
+    }
+    fn all_coords(dims: &[usize]) -> Vec<Vec<usize>> {
+        dims.iter().fold(vec![Vec::new()], |acc, &extent| {
+            acc.into_iter()
+                .flat_map(|prefix| {
+                    (0..extent).map(move |v| {
+                        let mut next = prefix.clone();
+                        next.push(v);
+                        next
+                    })
+                })
+                .collect()
+        })
+    }
+    fn neighbour_offsets(dims: usize) -> Vec<Vec<isize>> {
+        (0..dims)
+            .fold(vec![Vec::new()], |acc, _| {
+                acc.into_iter()
+                    .flat_map(|prefix| {
+                        (-1..=1).map(move |offset| {
+                            let mut next = prefix.clone();
+                            next.push(offset);
+                            next
+                        })
+                    })
+                    .collect()
             })
-            .map(|(x_offset, y_offset)| (x as isize + x_offset, y as isize + y_offset))
-            .map(|(x, y)| (x as usize, y as usize))
+            .into_iter()
+            .filter(|offset: &Vec<isize>| offset.iter().any(|&o| o != 0))
             .collect()
     }
-    fn new_blank((width, height): (usize, usize)) -> Tiles {
-        Tiles(
-            ((0..width).map(|_| {
-                ((0..height).map(|_| Tile {
+    fn neighbours(&self, coords: &[usize]) -> Vec<Vec<usize>> {
+        self.neighbours_iter(coords).collect()
+    }
+    fn neighbours_iter<'a>(&'a self, coords: &'a [usize]) -> impl Iterator<Item = Vec<usize>> + 'a {
+        self.offsets.iter().filter_map(move |offset| {
+            let mut neighbour = Vec::with_capacity(offset.len());
+            for (axis, &o) in offset.iter().enumerate() {
+                let v = coords[axis] as isize + o;
+                if v < 0 || v as usize >= self.dims[axis] {
+                    return None;
+                }
+                neighbour.push(v as usize);
+            }
+            Some(neighbour)
+        })
+    }
+    fn for_each_neighbour(&self, coords: &[usize], mut visit: impl FnMut(&[usize])) {
+        let mut buf = [0usize; MAX_DIMS];
+        let buf = &mut buf[..coords.len()];
+        'offsets: for offset in &self.offsets {
+            for (axis, &o) in offset.iter().enumerate() {
+                let v = coords[axis] as isize + o;
+                if v < 0 || v as usize >= self.dims[axis] {
+                    continue 'offsets;
+                }
+                buf[axis] = v as usize;
+            }
+            visit(buf);
+        }
+    }
+    fn new_blank(dims: Vec<usize>) -> Tiles {
+        assert!(dims.len() <= MAX_DIMS, "boards support at most {MAX_DIMS} dimensions");
+        let total = dims.iter().product();
+        let offsets = Self::neighbour_offsets(dims.len());
+        Tiles {
+            dims,
+            cells: (0..total)
+                .map(|_| Tile {
                     mode: TileMode::Hidden,
                     content: TileContent::Field(0),
-                }))
-                .collect()
-            }))
-            .collect(),
-        )
+                })
+                .collect(),
+            offsets,
+        }
     }
-    fn populate_mines(&mut self, mine_count: usize, ignore: (usize, usize)) {
-        let mut rng = rand::rng();
+    fn populate_mines(&mut self, mine_count: usize, ignore: &[usize], rng: &mut StdRng) {
         for _ in 0..mine_count {
             loop {
-                let x = rng.random_range(0..self.len());
-                let y = rng.random_range(0..self[0].len());
-                if (x, y) == ignore {
+                let coords: Vec<usize> = self.dims.iter().map(|&d| rng.random_range(0..d)).collect();
+                if coords == ignore {
                     continue;
                 }
-                if matches!(self[x][y].content, TileContent::Mine) {
+                if matches!(self[coords.as_slice()].content, TileContent::Mine) {
                     continue;
                 }
-                self[x][y].content = TileContent::Mine;
+                self[coords.as_slice()].content = TileContent::Mine;
                 break;
             }
         }
     }
-    fn reveal(&mut self, x: usize, y: usize) {
-        let tile = &mut self[x][y];
-        match tile.mode {
-            TileMode::Hidden => {
-                tile.mode = TileMode::Revealed;
-                let TileContent::Field(0) = tile.content else {
-                    return;
+    fn reveal(&mut self, coords: &[usize]) -> usize {
+        let mut worklist = VecDeque::from([coords.to_vec()]);
+        let mut revealed = 0;
+
+        while let Some(coords) = worklist.pop_front() {
+            let tile = &mut self[coords.as_slice()];
+            match tile.mode {
+                TileMode::Hidden => {
+                    tile.mode = TileMode::Revealed;
+                    revealed += 1;
+                    let TileContent::Field(0) = tile.content else {
+                        continue;
+                    };
+                }
+                TileMode::Flagged => continue,
+                TileMode::Revealed => {
+                    let TileContent::Field(mines) = tile.content else {
+                        unreachable!()
+                    };
+                    let mut flags = 0;
+                    self.for_each_neighbour(&coords, |n| {
+                        if matches!(self[n].mode, TileMode::Flagged) {
+                            flags += 1;
+                        }
+                    });
+                    if mines != flags as u8 {
+                        continue;
+                    }
+                }
+            }
+            for neighbour in self.neighbours_iter(&coords) {
+                let TileMode::Hidden = self[neighbour.as_slice()].mode else {
+                    continue;
                 };
+                worklist.push_back(neighbour);
             }
-            TileMode::Flagged => return,
-            TileMode::Revealed => {
-                let TileContent::Field(mines) = tile.content else {
-                    unreachable!()
+        }
+        revealed
+    }
+    fn constraint(
+        &self,
+        coords: &[usize],
+        extra_safe: &HashSet<Vec<usize>>,
+        extra_mines: &HashSet<Vec<usize>>,
+    ) -> Option<(HashSet<Vec<usize>>, usize)> {
+        let TileContent::Field(n) = self[coords].content else {
+            return None;
+        };
+        let is_known = matches!(self[coords].mode, TileMode::Revealed) || extra_safe.contains(coords);
+        if !is_known {
+            return None;
+        }
+        let mut flagged = 0;
+        self.for_each_neighbour(coords, |n| {
+            if matches!(self[n].mode, TileMode::Flagged) || extra_mines.contains(n) {
+                flagged += 1;
+            }
+        });
+        let unknown: HashSet<Vec<usize>> = self
+            .neighbours_iter(coords)
+            .filter(|n| {
+                matches!(self[n.as_slice()].mode, TileMode::Hidden)
+                    && !extra_safe.contains(n)
+                    && !extra_mines.contains(n)
+            })
+            .collect();
+        if unknown.is_empty() {
+            return None;
+        }
+        // flag() doesn't validate placement, so this can exceed n and underflow below
+        if flagged > n as usize {
+            return None;
+        }
+        Some((unknown, n as usize - flagged))
+    }
+    fn deduce(&self) -> Deduction {
+        let mut safe = HashSet::new();
+        let mut mines = HashSet::new();
+
+        loop {
+            let mut changed = false;
+            for coords in Self::all_coords(&self.dims) {
+                let Some((unknown, required)) = self.constraint(&coords, &safe, &mines) else {
+                    continue;
                 };
-                let flags = self
-                    .neighbours(x, y)
-                    .iter()
-                    .filter(|(x, y)| matches!(self[*x][*y].mode, TileMode::Flagged))
-                    .count();
-                if mines != flags as u8 {
-                    return;
+                if required == 0 {
+                    changed |= unknown.into_iter().any(|pos| safe.insert(pos));
+                } else if required == unknown.len() {
+                    changed |= unknown.into_iter().any(|pos| mines.insert(pos));
                 }
             }
+            if changed {
+                continue;
+            }
+
+            let constraints: Vec<_> = Self::all_coords(&self.dims)
+                .into_iter()
+                .filter_map(|coords| self.constraint(&coords, &safe, &mines))
+                .collect();
+
+            let mut subset_changed = false;
+            for (cells_a, required_a) in &constraints {
+                for (cells_b, required_b) in &constraints {
+                    if cells_a.len() >= cells_b.len() || !cells_a.is_subset(cells_b) {
+                        continue;
+                    }
+                    let diff: Vec<_> = cells_b.difference(cells_a).cloned().collect();
+                    let diff_required = required_b - required_a;
+                    if diff_required == 0 {
+                        subset_changed |= diff.into_iter().any(|pos| safe.insert(pos));
+                    } else if diff_required == diff.len() {
+                        subset_changed |= diff.into_iter().any(|pos| mines.insert(pos));
+                    }
+                }
+            }
+
+            if !subset_changed {
+                let guess_required = safe.is_empty() && mines.is_empty();
+                return Deduction {
+                    safe: safe.into_iter().collect(),
+                    mines: mines.into_iter().collect(),
+                    guess_required,
+                };
+            }
         }
-        for nb_pos in self.neighbours(x, y) {
-            let tile = &self[nb_pos.0][nb_pos.1];
-            let TileMode::Hidden = tile.mode else {
+    }
+    fn three_bv(&self) -> usize {
+        let mut visited = vec![false; self.cells.len()];
+        let mut bv3 = 0;
+
+        for coords in Self::all_coords(&self.dims) {
+            if visited[self.flat_index(&coords)] {
+                continue;
+            }
+            let TileContent::Field(0) = self[coords.as_slice()].content else {
                 continue;
             };
-            self.reveal(nb_pos.0, nb_pos.1);
+            bv3 += 1;
+            let mut worklist = VecDeque::from([coords]);
+            while let Some(coords) = worklist.pop_front() {
+                let idx = self.flat_index(&coords);
+                if visited[idx] {
+                    continue;
+                }
+                visited[idx] = true;
+                for neighbour in self.neighbours_iter(&coords) {
+                    let nidx = self.flat_index(&neighbour);
+                    if visited[nidx] {
+                        continue;
+                    }
+                    if matches!(self[neighbour.as_slice()].content, TileContent::Field(0)) {
+                        worklist.push_back(neighbour);
+                    } else {
+                        visited[nidx] = true;
+                    }
+                }
+            }
         }
+
+        for coords in Self::all_coords(&self.dims) {
+            let idx = self.flat_index(&coords);
+            if visited[idx] {
+                continue;
+            }
+            visited[idx] = true;
+            if matches!(self[coords.as_slice()].content, TileContent::Field(_)) {
+                bv3 += 1;
+            }
+        }
+
+        bv3
     }
-    fn new(options: &TilesOptions) -> Self {
-        let (width, height) = options.size;
+    fn compute_numbers(&mut self) {
+        for coords in Self::all_coords(&self.dims) {
+            if !matches!(self[coords.as_slice()].content, TileContent::Field(_)) {
+                continue;
+            }
+            let mut mines = 0;
+            self.for_each_neighbour(&coords, |n| {
+                if matches!(self[n].content, TileContent::Mine) {
+                    mines += 1;
+                }
+            });
+            self[coords.as_slice()].content = TileContent::Field(mines as u8);
+        }
+    }
+    fn first_unsolved_frontier(&self, starting_position: &[usize]) -> Option<Vec<usize>> {
+        let mut probe = self.clone();
+        probe.reveal(starting_position);
+        loop {
+            let deduction = probe.deduce();
+            if deduction.safe.is_empty() && deduction.mines.is_empty() {
+                break;
+            }
+            for coords in deduction.mines {
+                probe[coords.as_slice()].mode = TileMode::Flagged;
+            }
+            for coords in deduction.safe {
+                probe.reveal(&coords);
+            }
+        }
+        Self::all_coords(&probe.dims).into_iter().find(|coords| {
+            let stuck = matches!(probe[coords.as_slice()].content, TileContent::Field(_))
+                && matches!(probe[coords.as_slice()].mode, TileMode::Hidden);
+            stuck
+                && probe
+                    .neighbours_iter(coords)
+                    .any(|n| matches!(probe[n.as_slice()].mode, TileMode::Revealed))
+        })
+    }
+    fn relocate_mine_near(&mut self, blocker: &[usize], ignore: &[usize], rng: &mut StdRng) -> bool {
+        let Some(mine_pos) = self
+            .neighbours_iter(blocker)
+            .find(|n| matches!(self[n.as_slice()].content, TileContent::Mine))
+        else {
+            return false;
+        };
+
+        let protected = self.neighbours(ignore);
+        loop {
+            let coords: Vec<usize> = self.dims.iter().map(|&d| rng.random_range(0..d)).collect();
+            if coords == ignore || coords == mine_pos || protected.contains(&coords) {
+                continue;
+            }
+            if matches!(self[coords.as_slice()].content, TileContent::Mine) {
+                continue;
+            }
+            self[mine_pos.as_slice()].content = TileContent::Field(0);
+            self[coords.as_slice()].content = TileContent::Mine;
+            break;
+        }
+        self.compute_numbers();
+        true
+    }
+    fn new(options: &TilesOptions) -> (Self, u64) {
+        let size = options.size.iter().product::<usize>();
         assert!(
-            width * height > options.mine_count,
+            size > options.mine_count,
             "should at most place `width*height - 1` # of mines"
         );
-        let mut tiles = Self::new_blank((width, height));
-        tiles.populate_mines(options.mine_count, options.starting_position);
-        for x in 0..width {
-            for y in 0..height {
-                if !matches!(tiles[x][y].content, TileContent::Field(_)) {
-                    continue;
+        let effective_seed = options.seed.unwrap_or_else(|| rand::rng().random());
+        let mut rng = StdRng::seed_from_u64(effective_seed);
+
+        let mut tiles = Self::new_blank(options.size.clone());
+        tiles.populate_mines(options.mine_count, &options.starting_position, &mut rng);
+        tiles.compute_numbers();
+
+        if options.no_guess {
+            const MAX_ATTEMPTS: usize = 200;
+            for _ in 0..MAX_ATTEMPTS {
+                let Some(blocker) = tiles.first_unsolved_frontier(&options.starting_position)
+                else {
+                    break;
+                };
+                if !tiles.relocate_mine_near(&blocker, &options.starting_position, &mut rng) {
+                    break;
                 }
-                let mines = tiles
-                    .neighbours(x, y)
-                    .iter()
-                    .filter(|(x, y)| matches!(&tiles[*x][*y].content, TileContent::Mine))
-                    .count();
-                tiles[x][y].content = TileContent::Field(mines as u8);
             }
         }
-        tiles.reveal(options.starting_position.0, options.starting_position.1);
+
+        tiles.reveal(&options.starting_position);
+        (tiles, effective_seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3x3 board with a single mine revealed from the opposite corner.
+    fn corner_with_diagonal_mine() -> Tiles {
+        let mut tiles = Tiles::new_blank(vec![3, 3]);
+        let mine = [2usize, 2];
+        tiles[mine.as_slice()].content = TileContent::Mine;
+        tiles.compute_numbers();
+        tiles.reveal(&[0, 0]);
         tiles
     }
+
+    #[test]
+    fn over_flagged_neighbour_does_not_panic_in_constraint() {
+        // two wrongly flagged neighbours used to underflow `constraint`'s subtraction
+        let mut tiles = Tiles::new_blank(vec![3, 3]);
+        let centre = [1usize, 1];
+        let mine = [2usize, 2];
+        tiles[mine.as_slice()].content = TileContent::Mine;
+        tiles.compute_numbers();
+        tiles[centre.as_slice()].mode = TileMode::Revealed;
+        tiles[[0usize, 0].as_slice()].mode = TileMode::Flagged;
+        tiles[[1usize, 0].as_slice()].mode = TileMode::Flagged;
+
+        let deduction = tiles.deduce();
+        assert!(deduction.safe.is_empty());
+        assert!(deduction.mines.is_empty());
+    }
+
+    #[test]
+    fn deduce_flags_the_only_remaining_mine_neighbour() {
+        let tiles = corner_with_diagonal_mine();
+
+        let deduction = tiles.deduce();
+        assert_eq!(deduction.mines, vec![vec![2, 2]]);
+        assert!(deduction.safe.is_empty());
+        assert!(!deduction.guess_required);
+    }
+
+    #[test]
+    fn seeded_generation_is_deterministic() {
+        let options = TilesOptions {
+            size: vec![5, 5],
+            starting_position: vec![0, 0],
+            mine_count: 5,
+            no_guess: false,
+            seed: Some(42),
+        };
+        let (first, seed_a) = Tiles::new(&options);
+        let (second, seed_b) = Tiles::new(&options);
+        assert_eq!(seed_a, seed_b);
+        assert!(first.cells.iter().zip(second.cells.iter()).all(|(a, b)| {
+            match (&a.content, &b.content) {
+                (TileContent::Mine, TileContent::Mine) => true,
+                (TileContent::Field(x), TileContent::Field(y)) => x == y,
+                _ => false,
+            }
+        }));
+    }
+
+    #[test]
+    fn no_guess_generation_is_fully_solvable_for_seed_7() {
+        let options = TilesOptions {
+            size: vec![4, 4],
+            starting_position: vec![0, 0],
+            mine_count: 3,
+            no_guess: true,
+            seed: Some(7),
+        };
+        let (tiles, _) = Tiles::new(&options);
+        assert!(tiles.first_unsolved_frontier(&[0, 0]).is_none());
+    }
+
+    #[test]
+    fn three_bv_counts_a_single_open_region_as_one_click() {
+        let tiles = corner_with_diagonal_mine();
+        assert_eq!(tiles.three_bv(), 1);
+    }
 }
@@ -1,18 +1,30 @@
+use std::{collections::HashSet, io};
+
 use color_eyre::{
     Result,
     eyre::{Context, eyre},
 };
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
+    execute,
+};
 use ratatui::{
     DefaultTerminal, Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::Stylize,
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Paragraph},
 };
 
 use crate::game::{CursorDirection, Game, Tile, TileContent, TileMistake, TileMode};
+use crate::settings_menu::SettingsMenu;
+use crate::seven_segment::SevenSegment;
 mod game;
+mod settings_menu;
+mod seven_segment;
 
 fn main() -> Result<()> {
     let [width, height, mines]: [usize; 3] = std::env::args()
@@ -24,22 +36,57 @@ fn main() -> Result<()> {
 
     color_eyre::install()?;
     let terminal = ratatui::init();
-    let result = App::new((width, height), mines).run(terminal);
+    let result = App::new(vec![width, height], mines).run(terminal);
+    execute!(io::stdout(), DisableMouseCapture).ok();
     ratatui::restore();
     result
 }
 
+enum AppScreen {
+    Game,
+    Settings,
+}
+
 struct App {
     running: bool,
+    screen: AppScreen,
     game: Game,
+    settings: SettingsMenu,
+    board_area: Rect,
+    board_inner_area: Rect,
+    smiley_area: Rect,
+    show_hints: bool,
+    last_undo_summary: Option<String>,
+}
+
+const AXIS_X: usize = 0;
+const AXIS_Y: usize = 1;
+
+fn digits(n: i32, width: usize) -> Vec<u8> {
+    let max = 10i32.pow(width as u32) - 1;
+    let magnitude = n.clamp(0, max) as u32;
+    (0..width)
+        .rev()
+        .map(|place| ((magnitude / 10u32.pow(place as u32)) % 10) as u8)
+        .collect()
 }
 
 trait RenderTile {
-    fn render_tile(&self, is_selected: bool) -> ratatui::text::Span<'static>;
+    fn render_tile(
+        &self,
+        is_selected: bool,
+        is_highlighted: bool,
+        hint: Option<bool>,
+    ) -> ratatui::text::Span<'static>;
 }
 
 impl RenderTile for Tile {
-    fn render_tile(&self, is_selected: bool) -> ratatui::text::Span<'static> {
+    fn render_tile(
+        &self,
+        is_selected: bool,
+        is_highlighted: bool,
+        hint: Option<bool>,
+    ) -> ratatui::text::Span<'static> {
         let res = match (&self.mode, &self.content) {
             (TileMode::Hidden, _) => "-".white(),
             (TileMode::Flagged, _) => "î".red(),
@@ -61,6 +108,12 @@ impl RenderTile for Tile {
                 n.to_string().white().on_red()
             }
         };
+        let res = match hint {
+            Some(true) => res.on_red(),
+            Some(false) => res.on_green(),
+            None => res,
+        };
+        let res = if is_highlighted { res.on_dark_gray() } else { res };
         if is_selected {
             res.underlined()
         } else {
@@ -70,16 +123,25 @@ impl RenderTile for Tile {
 }
 
 impl App {
-    pub fn new(size: (usize, usize), mine_count: usize) -> Self {
+    pub fn new(size: Vec<usize>, mine_count: usize) -> Self {
+        let settings = SettingsMenu::new(size[AXIS_X], size[AXIS_Y], mine_count);
         Self {
             running: false,
+            screen: AppScreen::Game,
             game: Game::new(size, mine_count),
+            settings,
+            board_area: Rect::default(),
+            board_inner_area: Rect::default(),
+            smiley_area: Rect::default(),
+            show_hints: false,
+            last_undo_summary: None,
         }
     }
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         self.running = true;
         terminal.hide_cursor()?;
+        execute!(io::stdout(), EnableMouseCapture)?;
         while self.running {
             terminal.draw(|frame| self.render(frame))?;
             self.handle_crossterm_events()?;
@@ -88,28 +150,104 @@ impl App {
     }
 
     fn render(&mut self, frame: &mut Frame) {
-        let (game_width, game_height) = self.game.size;
+        match self.screen {
+            AppScreen::Game => self.render_game(frame),
+            AppScreen::Settings => {
+                let area = frame.area();
+                self.settings.render(frame, area);
+            }
+        }
+    }
+
+    fn highlighted_neighbours(&self) -> HashSet<Vec<usize>> {
+        let tile = self.game.tile_at(&self.game.cursor);
+        let (TileMode::Revealed, TileContent::Field(n)) = (&tile.mode, &tile.content) else {
+            return HashSet::new();
+        };
+        if *n == 0 {
+            return HashSet::new();
+        }
+        self.game
+            .neighbours(&self.game.cursor)
+            .into_iter()
+            .filter(|coords| matches!(self.game.tile_at(coords).mode, TileMode::Hidden))
+            .collect()
+    }
+
+    fn undo(&mut self) {
+        let Some(seed) = self.game.seed() else {
+            return;
+        };
+        let mut history = self.game.history().to_vec();
+        if history.is_empty() {
+            return;
+        }
+        history.pop();
+        let replayed_len = history.len();
+        let mut replay = Game::replay(
+            self.game.size.clone(),
+            self.game.mine_count(),
+            self.game.no_guess(),
+            seed,
+            history,
+        );
+        let mut changed_tiles = 0;
+        while let Some(frame) = replay.step() {
+            changed_tiles += frame
+                .before
+                .iter()
+                .zip(frame.after.iter())
+                .filter(|(before, after)| before.mode != after.mode)
+                .count();
+        }
+        debug_assert_eq!(replay.game().history().len(), replayed_len);
+        self.last_undo_summary = Some(format!("undo: reverted {changed_tiles} tile change(s)"));
+        self.game = replay.into_game();
+    }
+
+    fn hints(&self) -> (HashSet<Vec<usize>>, HashSet<Vec<usize>>) {
+        if !self.show_hints {
+            return (HashSet::new(), HashSet::new());
+        }
+        let deduction = self.game.deduce();
+        (
+            deduction.safe.into_iter().collect(),
+            deduction.mines.into_iter().collect(),
+        )
+    }
+
+    fn render_game(&mut self, frame: &mut Frame) {
+        let (game_width, game_height) = (self.game.size[AXIS_X], self.game.size[AXIS_Y]);
 
         let board_width = 2 + (3 * game_width) as u16;
         let board_height = 2 + game_height as u16;
 
-        let (time, status) = self.game.status();
+        let (time, status, score) = self.game.status();
         let secs = time.as_secs() % 60;
-        let mins = (time.as_secs() - secs) / 60;
-
-        let hud = [Line::default().spans([
-            format!("{mins}:{secs:02}").white(),
-            " ".gray(),
-            format!("{}", self.game.unflagged_bombs()).on_red(),
-            " ".gray(),
-            match status {
-                game::GameStatus::Initial => ":)",
-                game::GameStatus::Won => ":D",
-                game::GameStatus::Lost => ":(",
-                game::GameStatus::Ongoing => ":o",
-            }
-            .white(),
-        ])];
+        let mins = ((time.as_secs() - secs) / 60).min(99);
+
+        let time_digits = [(mins / 10) as u8, (mins % 10) as u8, (secs / 10) as u8, (secs % 10) as u8];
+        let time_display = SevenSegment::render(&time_digits);
+        let bombs_display = SevenSegment::render(&digits(self.game.unflagged_bombs(), 3));
+        let smiley = match status {
+            game::GameStatus::Initial => ":)",
+            game::GameStatus::Won => ":D",
+            game::GameStatus::Lost => ":(",
+            game::GameStatus::Ongoing => ":o",
+        };
+
+        let hud: [Line; 3] = std::array::from_fn(|row| {
+            let mut spans = time_display[row].spans.clone();
+            spans.push(Span::raw("   "));
+            spans.extend(bombs_display[row].spans.clone());
+            Line::from(spans)
+        });
+        let hud_width = hud[0].width() as u16;
+        const SMILEY_GAP: u16 = 3;
+        let smiley_width = smiley.chars().count() as u16;
+
+        let highlighted = self.highlighted_neighbours();
+        let (hint_safe, hint_mines) = self.hints();
 
         let board_area = Rect::new(
             (frame.area().width - board_width) / 2,
@@ -117,9 +255,11 @@ impl App {
             board_width,
             board_height,
         );
+        self.board_area = board_area;
         {
             let board = Block::bordered().border_type(ratatui::widgets::BorderType::Rounded);
             let board_inner_area = board.inner(board_area);
+            self.board_inner_area = board_inner_area;
 
             let hori = Layout::default()
                 .constraints((0..game_width).map(|_| Constraint::Length(3)))
@@ -133,12 +273,20 @@ impl App {
                     .split(*hori);
 
                 for (y, hori) in vert.iter().enumerate() {
+                    let coords = vec![x, y];
+                    let hint = if hint_mines.contains(&coords) {
+                        Some(true)
+                    } else if hint_safe.contains(&coords) {
+                        Some(false)
+                    } else {
+                        None
+                    };
                     frame.render_widget(
-                        Paragraph::new(
-                            self.game
-                                .tile_at(x, y)
-                                .render_tile(x == self.game.cursor.0 && y == self.game.cursor.1),
-                        )
+                        Paragraph::new(self.game.tile_at(&[x, y]).render_tile(
+                            x == self.game.cursor[AXIS_X] && y == self.game.cursor[AXIS_Y],
+                            highlighted.contains(&coords),
+                            hint,
+                        ))
                         .block(Block::new().on_black())
                         .centered(),
                         *hori,
@@ -148,14 +296,57 @@ impl App {
             frame.render_widget(board, board_area);
         }
         let text_y = board_area.y + board_area.height;
+        let total_width = hud_width + SMILEY_GAP + smiley_width;
+        let start_x = (frame.area().width - total_width) / 2;
         for (offset, hud) in hud.iter().enumerate() {
-            let area = Rect::new(
-                (frame.area().width - hud.width() as u16) / 2,
-                text_y + offset as u16,
-                hud.width() as u16,
+            let area = Rect::new(start_x, text_y + offset as u16, hud_width, 1);
+            frame.render_widget(hud, area);
+        }
+        let smiley_area = Rect::new(
+            start_x + hud_width + SMILEY_GAP,
+            text_y + 1,
+            smiley_width,
+            1,
+        );
+        self.smiley_area = smiley_area;
+        frame.render_widget(Paragraph::new(smiley.white()), smiley_area);
+
+        if let Some(seed) = self.game.seed() {
+            let seed_line = format!("seed: {seed}");
+            let seed_width = seed_line.chars().count() as u16;
+            let seed_area = Rect::new(
+                (frame.area().width - seed_width) / 2,
+                text_y + hud.len() as u16 + 1,
+                seed_width,
                 1,
             );
-            frame.render_widget(hud, area);
+            frame.render_widget(Paragraph::new(seed_line.dark_gray()), seed_area);
+        }
+
+        if let Some(summary) = &self.last_undo_summary {
+            let summary_width = summary.chars().count() as u16;
+            let summary_area = Rect::new(
+                (frame.area().width - summary_width) / 2,
+                text_y + hud.len() as u16 + 2,
+                summary_width,
+                1,
+            );
+            frame.render_widget(Paragraph::new(summary.clone().dark_gray()), summary_area);
+        }
+
+        if let Some(score) = score {
+            let score_line = format!(
+                "3bv: {}  clicks: {}  3bv/s: {:.2}",
+                score.bv3, score.clicks, score.bv3_per_second
+            );
+            let score_width = score_line.chars().count() as u16;
+            let score_area = Rect::new(
+                (frame.area().width - score_width) / 2,
+                text_y + hud.len() as u16 + 3,
+                score_width,
+                1,
+            );
+            frame.render_widget(Paragraph::new(score_line.white()), score_area);
         }
     }
 
@@ -165,27 +356,127 @@ impl App {
         }
         match event::read()? {
             Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
+            Event::Mouse(mouse) => self.on_mouse_event(mouse),
             _ => {}
         }
         Ok(())
     }
 
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        let AppScreen::Game = self.screen else {
+            return;
+        };
+
+        let smiley = self.smiley_area;
+        let on_smiley = mouse.column >= smiley.x
+            && mouse.column < smiley.x + smiley.width
+            && mouse.row >= smiley.y
+            && mouse.row < smiley.y + smiley.height;
+        if on_smiley {
+            if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                self.game.reset();
+            }
+            return;
+        }
+
+        let inner = self.board_inner_area;
+        if mouse.column < inner.x
+            || mouse.row < inner.y
+            || mouse.column >= inner.x + inner.width
+            || mouse.row >= inner.y + inner.height
+        {
+            return;
+        }
+        let tile_x = ((mouse.column - inner.x) / 3) as usize;
+        let tile_y = (mouse.row - inner.y) as usize;
+        if tile_x >= self.game.size[AXIS_X] || tile_y >= self.game.size[AXIS_Y] {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Moved => {
+                self.game.cursor[AXIS_X] = tile_x;
+                self.game.cursor[AXIS_Y] = tile_y;
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.game.cursor[AXIS_X] = tile_x;
+                self.game.cursor[AXIS_Y] = tile_y;
+                self.game.reveal();
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                self.game.cursor[AXIS_X] = tile_x;
+                self.game.cursor[AXIS_Y] = tile_y;
+                self.game.flag();
+            }
+            _ => {}
+        }
+    }
+
     fn on_key_event(&mut self, key: KeyEvent) {
+        match self.screen {
+            AppScreen::Game => self.on_game_key_event(key),
+            AppScreen::Settings => self.on_settings_key_event(key),
+        }
+    }
+
+    fn on_game_key_event(&mut self, key: KeyEvent) {
         match (key.modifiers, key.code) {
             (_, KeyCode::Esc | KeyCode::Char('q'))
             | (KeyModifiers::CONTROL, KeyCode::Char('c' | 'C')) => self.quit(),
-            (_, KeyCode::Up | KeyCode::Char('w')) => self.game.move_cursor(&CursorDirection::Up),
+            (_, KeyCode::Up | KeyCode::Char('w')) => {
+                self.game.move_cursor(&CursorDirection::Decrease(AXIS_Y));
+            }
             (_, KeyCode::Left | KeyCode::Char('a')) => {
-                self.game.move_cursor(&CursorDirection::Left);
+                self.game.move_cursor(&CursorDirection::Decrease(AXIS_X));
             }
             (_, KeyCode::Down | KeyCode::Char('s')) => {
-                self.game.move_cursor(&CursorDirection::Down);
+                self.game.move_cursor(&CursorDirection::Increase(AXIS_Y));
             }
             (_, KeyCode::Right | KeyCode::Char('d')) => {
-                self.game.move_cursor(&CursorDirection::Right);
+                self.game.move_cursor(&CursorDirection::Increase(AXIS_X));
             }
             (_, KeyCode::Char(' ')) => self.game.flag(),
-            (_, KeyCode::Enter) => self.game.reveal(),
+            (_, KeyCode::Enter) => {
+                if matches!(self.game.tile_at(&self.game.cursor).mode, TileMode::Revealed) {
+                    self.game.chord();
+                } else {
+                    self.game.reveal();
+                }
+            }
+            (_, KeyCode::Char('c')) => self.game.chord(),
+            (_, KeyCode::Char('r')) => self.game.reset(),
+            (_, KeyCode::Char('h')) => {
+                self.game.solve_step();
+            }
+            (_, KeyCode::Char('p')) => self.show_hints = !self.show_hints,
+            (_, KeyCode::Char('u')) => self.undo(),
+            (_, KeyCode::Char('m')) => {
+                self.settings = SettingsMenu::with_options(
+                    self.game.size[AXIS_X],
+                    self.game.size[AXIS_Y],
+                    self.game.mine_count(),
+                    self.game.no_guess(),
+                    self.game.seed(),
+                );
+                self.screen = AppScreen::Settings;
+            }
+            _ => {}
+        }
+    }
+
+    fn on_settings_key_event(&mut self, key: KeyEvent) {
+        match (key.modifiers, key.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c' | 'C')) => self.quit(),
+            (_, KeyCode::Esc) => self.screen = AppScreen::Game,
+            (_, KeyCode::Up | KeyCode::Char('w')) => self.settings.move_up(),
+            (_, KeyCode::Down | KeyCode::Char('s')) => self.settings.move_down(),
+            (_, KeyCode::Left | KeyCode::Char('a')) => self.settings.adjust(-1),
+            (_, KeyCode::Right | KeyCode::Char('d')) => self.settings.adjust(1),
+            (_, KeyCode::Enter) => {
+                let (size, mine_count, no_guess, seed) = self.settings.confirm();
+                self.game = Game::with_options(size, mine_count, no_guess, seed);
+                self.screen = AppScreen::Game;
+            }
             _ => {}
         }
     }
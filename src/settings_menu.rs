@@ -0,0 +1,164 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Block, BorderType, Paragraph},
+};
+
+struct Preset {
+    name: &'static str,
+    width: usize,
+    height: usize,
+    mines: usize,
+}
+
+const PRESETS: [Preset; 3] = [
+    Preset {
+        name: "Beginner",
+        width: 9,
+        height: 9,
+        mines: 10,
+    },
+    Preset {
+        name: "Intermediate",
+        width: 16,
+        height: 16,
+        mines: 40,
+    },
+    Preset {
+        name: "Expert",
+        width: 30,
+        height: 16,
+        mines: 99,
+    },
+];
+
+const CUSTOM_ROWS: usize = 5;
+const ROW_COUNT: usize = PRESETS.len() + CUSTOM_ROWS;
+
+const CUSTOM_WIDTH_ROW: usize = PRESETS.len();
+const CUSTOM_HEIGHT_ROW: usize = PRESETS.len() + 1;
+const CUSTOM_MINES_ROW: usize = PRESETS.len() + 2;
+const NO_GUESS_ROW: usize = PRESETS.len() + 3;
+const SEED_ROW: usize = PRESETS.len() + 4;
+
+pub struct SettingsMenu {
+    selected: usize,
+    custom_width: usize,
+    custom_height: usize,
+    custom_mines: usize,
+    no_guess: bool,
+    seed: u64,
+}
+
+impl SettingsMenu {
+    pub fn new(width: usize, height: usize, mines: usize) -> Self {
+        Self::with_options(width, height, mines, false, None)
+    }
+
+    pub fn with_options(
+        width: usize,
+        height: usize,
+        mines: usize,
+        no_guess: bool,
+        seed: Option<u64>,
+    ) -> Self {
+        Self {
+            selected: 0,
+            custom_width: width,
+            custom_height: height,
+            custom_mines: mines,
+            no_guess,
+            seed: seed.unwrap_or(0),
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        self.selected = (self.selected + 1).min(ROW_COUNT - 1);
+    }
+
+    pub fn adjust(&mut self, delta: isize) {
+        if self.selected == NO_GUESS_ROW {
+            self.no_guess = !self.no_guess;
+            return;
+        }
+        if self.selected == SEED_ROW {
+            self.seed = (self.seed as i64 + delta as i64).max(0) as u64;
+            return;
+        }
+        let field = match self.selected {
+            CUSTOM_WIDTH_ROW => &mut self.custom_width,
+            CUSTOM_HEIGHT_ROW => &mut self.custom_height,
+            CUSTOM_MINES_ROW => &mut self.custom_mines,
+            _ => return,
+        };
+        *field = (*field as isize + delta).max(1) as usize;
+
+        let max_mines = self.custom_width * self.custom_height - 1;
+        self.custom_mines = self.custom_mines.min(max_mines);
+    }
+
+    pub fn confirm(&self) -> (Vec<usize>, usize, bool, Option<u64>) {
+        let seed = (self.seed != 0).then_some(self.seed);
+        if let Some(preset) = PRESETS.get(self.selected) {
+            return (vec![preset.width, preset.height], preset.mines, self.no_guess, seed);
+        }
+        (
+            vec![self.custom_width, self.custom_height],
+            self.custom_mines,
+            self.no_guess,
+            seed,
+        )
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(" new game ");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows: Vec<Line> = PRESETS
+            .iter()
+            .map(|preset| {
+                format!(
+                    "{} ({}x{}, {})",
+                    preset.name, preset.width, preset.height, preset.mines
+                )
+            })
+            .map(Line::raw)
+            .chain([
+                Line::raw(format!("Width:  {}", self.custom_width)),
+                Line::raw(format!("Height: {}", self.custom_height)),
+                Line::raw(format!("Mines:  {}", self.custom_mines)),
+                Line::raw(format!(
+                    "No-guess: {}",
+                    if self.no_guess { "on" } else { "off" }
+                )),
+                Line::raw(format!(
+                    "Seed:   {}",
+                    if self.seed == 0 {
+                        "random".to_string()
+                    } else {
+                        self.seed.to_string()
+                    }
+                )),
+            ])
+            .enumerate()
+            .map(|(i, line)| {
+                if i == self.selected {
+                    line.reversed()
+                } else {
+                    line.white()
+                }
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(rows), inner);
+    }
+}
@@ -0,0 +1,52 @@
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+};
+
+const SEGMENTS: [[bool; 7]; 10] = [
+    [true, true, true, true, true, true, false],
+    [false, true, true, false, false, false, false],
+    [true, true, false, true, true, false, true],
+    [true, true, true, true, false, false, true],
+    [false, true, true, false, false, true, true],
+    [true, false, true, true, false, true, true],
+    [true, false, true, true, true, true, true],
+    [true, true, true, false, false, false, false],
+    [true, true, true, true, true, true, true],
+    [true, true, true, true, false, true, true],
+];
+
+pub struct SevenSegment;
+
+impl SevenSegment {
+    pub fn render(digits: &[u8]) -> [Line<'static>; 3] {
+        let mut rows: [Vec<Span<'static>>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+        for (i, &digit) in digits.iter().enumerate() {
+            if i > 0 {
+                for row in &mut rows {
+                    row.push(Span::raw(" "));
+                }
+            }
+            let [a, b, c, d, e, f, g] = SEGMENTS[digit as usize % 10];
+            rows[0].push(segment(" ", false));
+            rows[0].push(segment("▀", a));
+            rows[0].push(segment(" ", false));
+            rows[1].push(segment("▌", f));
+            rows[1].push(segment("▀", g));
+            rows[1].push(segment("▐", b));
+            rows[2].push(segment("▌", e));
+            rows[2].push(segment("▄", d));
+            rows[2].push(segment("▐", c));
+        }
+        let [top, middle, bottom] = rows;
+        [Line::from(top), Line::from(middle), Line::from(bottom)]
+    }
+}
+
+fn segment(glyph: &'static str, lit: bool) -> Span<'static> {
+    if lit {
+        glyph.red()
+    } else {
+        glyph.dark_gray()
+    }
+}